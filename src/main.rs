@@ -7,16 +7,23 @@ use winit::{
 };
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+mod post;
+mod texture;
 
 // Define vertex data structure
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 struct Vertex {
-    position: [f32; 2],
+    position: [f32; 3],
+    tex_coords: [f32; 2],
 }
 
 impl Vertex {
-    const ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x2];
+    // Location 1 is taken by InstanceRaw on vertex buffer slot 1.
+    const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+        wgpu::vertex_attr_array![0 => Float32x3, 2 => Float32x2];
 
     fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
@@ -27,6 +34,44 @@ impl Vertex {
     }
 }
 
+// Per-instance transform applied on top of a shape's mesh, so one vertex/index
+// buffer can be drawn many times at different places/scales/rotations.
+#[derive(Copy, Clone, Debug)]
+struct Instance {
+    position: [f32; 2],
+    scale: f32,
+    rotation: f32,
+}
+
+// GPU-friendly packing of `Instance`: offset.xy, scale, rotation, matching the
+// compact 2D layout (as opposed to a full 4x4 model matrix).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct InstanceRaw {
+    offset_scale_rotation: [f32; 4],
+}
+
+impl Instance {
+    fn to_raw(self) -> InstanceRaw {
+        InstanceRaw {
+            offset_scale_rotation: [self.position[0], self.position[1], self.scale, self.rotation],
+        }
+    }
+}
+
+impl InstanceRaw {
+    // Shader locations continue after Vertex::desc()'s locations (0).
+    const ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![1 => Float32x4];
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
 // New shape-related structures
 #[derive(Debug)]
 enum Shape {
@@ -35,39 +80,53 @@ enum Shape {
 }
 
 impl Shape {
-    fn generate_vertices(&self) -> Vec<Vertex> {
+    /// Builds an indexed triangle-fan mesh. For a circle this emits exactly
+    /// `segments + 1` unique vertices (center + one ring point per segment) and
+    /// a fan index list that wraps the last ring point back to the first.
+    /// Returns an empty mesh for `segments < 3`.
+    fn generate_mesh(&self) -> (Vec<Vertex>, Vec<u16>) {
         match self {
             Shape::Circle { center, radius, segments } => {
-                let mut vertices = Vec::with_capacity((*segments as usize + 2) * 3);
-                
-                // Generate circle vertices
+                if *segments < 3 {
+                    return (Vec::new(), Vec::new());
+                }
+
+                let mut vertices = Vec::with_capacity(*segments as usize + 1);
+                let mut indices = Vec::with_capacity(*segments as usize * 3);
+
+                // Index 0: center, mapped to the UV disc's center
+                vertices.push(Vertex { position: [center[0], center[1], 0.0], tex_coords: [0.5, 0.5] });
+
+                // Indices 1..=segments: one point per ring segment, mapped onto
+                // a unit disc UV centered at (0.5, 0.5)
                 for i in 0..*segments {
-                    // Add center vertex
-                    vertices.push(Vertex { position: *center });
-                    
-                    // Add first point of the triangle
-                    let angle1 = (i as f32 * 2.0 * std::f32::consts::PI) / *segments as f32;
-                    let x1 = center[0] + radius * angle1.cos();
-                    let y1 = center[1] + radius * angle1.sin();
-                    vertices.push(Vertex { position: [x1, y1] });
-                    
-                    // Add second point of the triangle
-                    let angle2 = ((i + 1) as f32 * 2.0 * std::f32::consts::PI) / *segments as f32;
-                    let x2 = center[0] + radius * angle2.cos();
-                    let y2 = center[1] + radius * angle2.sin();
-                    vertices.push(Vertex { position: [x2, y2] });
+                    let angle = (i as f32 * 2.0 * std::f32::consts::PI) / *segments as f32;
+                    let x = center[0] + radius * angle.cos();
+                    let y = center[1] + radius * angle.sin();
+                    vertices.push(Vertex {
+                        position: [x, y, 0.0],
+                        tex_coords: [angle.cos() * 0.5 + 0.5, angle.sin() * 0.5 + 0.5],
+                    });
+                }
+
+                for i in 1..=*segments {
+                    let next = if i == *segments { 1 } else { i + 1 };
+                    indices.push(0u16);
+                    indices.push(i as u16);
+                    indices.push(next as u16);
                 }
-                vertices
+
+                (vertices, indices)
             }
         }
     }
 }
 
-// Add this after the Vertex struct
+// Packs the camera's view-projection matrix for the shader.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 struct Uniforms {
-    aspect_ratio: f32,
+    view_proj: [[f32; 4]; 4],
 }
 
 // Add this new struct after the Uniforms struct
@@ -77,6 +136,112 @@ struct MouseState {
     position: [f32; 2],
 }
 
+// Orbit/fly camera following the learn-wgpu tutorial7 pattern: eye/target/up
+// describe the view, fovy/znear/zfar the perspective projection.
+struct Camera {
+    eye: glam::Vec3,
+    target: glam::Vec3,
+    up: glam::Vec3,
+    aspect: f32,
+    fovy_degrees: f32,
+    znear: f32,
+    zfar: f32,
+}
+
+impl Camera {
+    fn build_view_projection_matrix(&self) -> glam::Mat4 {
+        let view = glam::Mat4::look_at_rh(self.eye, self.target, self.up);
+        let proj = glam::Mat4::perspective_rh(self.fovy_degrees.to_radians(), self.aspect, self.znear, self.zfar);
+        proj * view
+    }
+}
+
+// Tracks which WASD/QE keys are currently held and nudges the camera each
+// frame, rather than snapping it on individual key events.
+struct CameraController {
+    speed: f32,
+    forward_pressed: bool,
+    backward_pressed: bool,
+    left_pressed: bool,
+    right_pressed: bool,
+    up_pressed: bool,
+    down_pressed: bool,
+}
+
+impl CameraController {
+    fn new(speed: f32) -> Self {
+        Self {
+            speed,
+            forward_pressed: false,
+            backward_pressed: false,
+            left_pressed: false,
+            right_pressed: false,
+            up_pressed: false,
+            down_pressed: false,
+        }
+    }
+
+    fn process_keyboard(&mut self, key: KeyCode, pressed: bool) -> bool {
+        match key {
+            KeyCode::KeyW | KeyCode::ArrowUp => {
+                self.forward_pressed = pressed;
+                true
+            }
+            KeyCode::KeyS | KeyCode::ArrowDown => {
+                self.backward_pressed = pressed;
+                true
+            }
+            KeyCode::KeyA | KeyCode::ArrowLeft => {
+                self.left_pressed = pressed;
+                true
+            }
+            KeyCode::KeyD | KeyCode::ArrowRight => {
+                self.right_pressed = pressed;
+                true
+            }
+            KeyCode::KeyE => {
+                self.up_pressed = pressed;
+                true
+            }
+            KeyCode::KeyQ => {
+                self.down_pressed = pressed;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn update_camera(&self, camera: &mut Camera) {
+        let forward = camera.target - camera.eye;
+        let forward_norm = forward.normalize();
+        let forward_mag = forward.length();
+        let right = forward_norm.cross(camera.up);
+
+        if self.forward_pressed && forward_mag > self.speed {
+            camera.eye += forward_norm * self.speed;
+        }
+        if self.backward_pressed {
+            camera.eye -= forward_norm * self.speed;
+        }
+        if self.right_pressed {
+            camera.eye += right * self.speed;
+            camera.target += right * self.speed;
+        }
+        if self.left_pressed {
+            camera.eye -= right * self.speed;
+            camera.target -= right * self.speed;
+        }
+        if self.up_pressed {
+            camera.eye += camera.up * self.speed;
+            camera.target += camera.up * self.speed;
+        }
+        if self.down_pressed {
+            camera.eye -= camera.up * self.speed;
+            camera.target -= camera.up * self.speed;
+        }
+    }
+}
+
 // Update the App struct to include the new uniform buffer and bind group
 struct App {
     window: Option<Arc<Window>>,
@@ -85,12 +250,33 @@ struct App {
     queue: Option<wgpu::Queue>,
     render_pipeline: Option<wgpu::RenderPipeline>,
     vertex_buffer: Option<wgpu::Buffer>,
+    index_buffer: Option<wgpu::Buffer>,
+    instance_buffer: Option<wgpu::Buffer>,
     config: Option<wgpu::SurfaceConfiguration>,
     shapes: Vec<Shape>,
+    instances: Vec<Instance>,
     num_vertices: u32,
+    num_indices: u32,
+    num_instances: u32,
     uniform_buffer: Option<wgpu::Buffer>,
+    mouse_buffer: Option<wgpu::Buffer>,
     uniform_bind_group: Option<wgpu::BindGroup>,
     mouse_state: MouseState,
+    depth_texture: Option<wgpu::Texture>,
+    depth_view: Option<wgpu::TextureView>,
+    camera: Camera,
+    camera_controller: CameraController,
+    circle_texture: Option<texture::Texture>,
+    texture_bind_group: Option<wgpu::BindGroup>,
+    // Shapes render into this offscreen target instead of the swapchain, so
+    // the post-processing chain below has something to sample from.
+    offscreen_texture: Option<wgpu::Texture>,
+    offscreen_view: Option<wgpu::TextureView>,
+    // Ping-ponged between post passes; the final pass writes straight to the
+    // swapchain view instead of into one of these.
+    ping_pong_textures: Vec<wgpu::Texture>,
+    ping_pong_views: Vec<wgpu::TextureView>,
+    post_passes: Vec<post::PostPass>,
 }
 
 impl App {
@@ -102,23 +288,126 @@ impl App {
             queue: None,
             render_pipeline: None,
             vertex_buffer: None,
+            index_buffer: None,
+            instance_buffer: None,
             config: None,
             shapes: Vec::new(),
+            instances: Vec::new(),
             num_vertices: 0,
+            num_indices: 0,
+            num_instances: 0,
             uniform_buffer: None,
+            mouse_buffer: None,
             uniform_bind_group: None,
             mouse_state: MouseState { position: [0.0, 0.0] },
+            depth_texture: None,
+            depth_view: None,
+            camera: Camera {
+                eye: glam::Vec3::new(0.0, 0.0, 2.0),
+                target: glam::Vec3::ZERO,
+                up: glam::Vec3::Y,
+                aspect: 1.0,
+                fovy_degrees: 45.0,
+                znear: 0.1,
+                zfar: 100.0,
+            },
+            camera_controller: CameraController::new(0.02),
+            circle_texture: None,
+            texture_bind_group: None,
+            offscreen_texture: None,
+            offscreen_view: None,
+            ping_pong_textures: Vec::new(),
+            ping_pong_views: Vec::new(),
+            post_passes: Vec::new(),
         }
     }
 
-    fn update_uniform_buffer(&self, width: u32, height: u32) {
+    /// Appends a post-processing pass to the end of the chain, compiling
+    /// `wgsl_src` as its fragment shader (see `post::PostPass`). No-op if
+    /// wgpu hasn't been initialized yet.
+    pub fn push_post_pass(&mut self, name: &str, wgsl_src: &str) {
+        let (Some(device), Some(config)) = (self.device.as_ref(), self.config.as_ref()) else {
+            return;
+        };
+        self.post_passes.push(post::PostPass::new(device, config.format, name, wgsl_src));
+    }
+
+    fn create_render_target(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        config: &wgpu::SurfaceConfiguration,
+        label: &str,
+    ) -> (wgpu::Texture, wgpu::TextureView) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: config.width.max(1),
+                height: config.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            // COPY_SRC lets this target be used as the source of the
+            // empty-post_passes fallback copy in `render_frame`.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (texture, view)
+    }
+
+    fn update_uniform_buffer(&self) {
         if let (Some(queue), Some(uniform_buffer)) = (&self.queue, &self.uniform_buffer) {
-            let aspect_ratio = height as f32 / width as f32;
-            let uniforms = Uniforms { aspect_ratio };
+            let uniforms = Uniforms {
+                view_proj: self.camera.build_view_projection_matrix().to_cols_array_2d(),
+            };
             queue.write_buffer(uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
         }
     }
 
+    fn create_depth_texture(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> (wgpu::Texture, wgpu::TextureView) {
+        let size = wgpu::Extent3d {
+            width: config.width.max(1),
+            height: config.height.max(1),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Depth Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (texture, view)
+    }
+
+    fn update_mouse_buffer(&self) {
+        if let (Some(queue), Some(mouse_buffer)) = (&self.queue, &self.mouse_buffer) {
+            queue.write_buffer(mouse_buffer, 0, bytemuck::cast_slice(&[self.mouse_state]));
+        }
+    }
+
+    /// Converts a window-space cursor position (pixels, origin top-left) into
+    /// normalized device coordinates (-1..1, origin center, Y up).
+    fn window_position_to_ndc(&self, position: &PhysicalPosition<f64>) -> [f32; 2] {
+        let (width, height) = self
+            .config
+            .as_ref()
+            .map(|config| (config.width.max(1), config.height.max(1)))
+            .unwrap_or((1, 1));
+        let x = (position.x / width as f64) * 2.0 - 1.0;
+        let y = 1.0 - (position.y / height as f64) * 2.0;
+        [x as f32, y as f32]
+    }
+
 
     async fn initialize_wgpu(&mut self, window: Arc<Window>) {
         // Create instance
@@ -159,12 +448,18 @@ impl App {
             segments: 32,
         }];
 
-        // Generate vertices for all shapes
+        // Generate an indexed mesh for all shapes, offsetting each shape's indices
+        // by the vertices already emitted by earlier shapes.
         let mut vertices = Vec::new();
+        let mut indices = Vec::new();
         for shape in &self.shapes {
-            vertices.extend(shape.generate_vertices());
+            let (shape_vertices, shape_indices) = shape.generate_mesh();
+            let base = vertices.len() as u16;
+            indices.extend(shape_indices.into_iter().map(|i| i + base));
+            vertices.extend(shape_vertices);
         }
         self.num_vertices = vertices.len() as u32;
+        self.num_indices = indices.len() as u32;
 
         // Create vertex buffer with the generated vertices
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -173,6 +468,39 @@ impl App {
             usage: wgpu::BufferUsages::VERTEX,
         });
 
+        // Create index buffer with the generated triangle-fan indices
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Index Buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        // Lay the single circle mesh out as a 10x10 grid of instances, each
+        // drawn from the same vertex/index buffer via a per-instance transform.
+        const GRID_SIZE: i32 = 10;
+        const GRID_SPACING: f32 = 0.15;
+        self.instances = (0..GRID_SIZE)
+            .flat_map(|y| {
+                (0..GRID_SIZE).map(move |x| {
+                    let offset_x = (x - GRID_SIZE / 2) as f32 * GRID_SPACING;
+                    let offset_y = (y - GRID_SIZE / 2) as f32 * GRID_SPACING;
+                    Instance {
+                        position: [offset_x, offset_y],
+                        scale: 1.0,
+                        rotation: 0.0,
+                    }
+                })
+            })
+            .collect();
+        self.num_instances = self.instances.len() as u32;
+
+        let instance_data: Vec<InstanceRaw> = self.instances.iter().copied().map(Instance::to_raw).collect();
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Instance Buffer"),
+            contents: bytemuck::cast_slice(&instance_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
         // Create shader
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Shader"),
@@ -180,8 +508,9 @@ impl App {
         });
 
         // Create uniform buffer
+        self.camera.aspect = window.inner_size().width as f32 / window.inner_size().height as f32;
         let uniforms = Uniforms {
-            aspect_ratio: window.inner_size().height as f32 / window.inner_size().width as f32,
+            view_proj: self.camera.build_view_projection_matrix().to_cols_array_2d(),
         };
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Uniform Buffer"),
@@ -189,35 +518,71 @@ impl App {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
+        // Create mouse uniform buffer, updated on every CursorMoved
+        let mouse_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mouse Buffer"),
+            contents: bytemuck::cast_slice(&[self.mouse_state]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
         // Create bind group layout
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Bind Group Layout"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
                 },
-                count: None,
-            }],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
         });
 
         // Create bind group
         let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Uniform Bind Group"),
             layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: uniform_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: mouse_buffer.as_entire_binding(),
+                },
+            ],
         });
 
+        // Load the default circle texture and build its bind group (group 1)
+        let circle_texture = texture::Texture::from_bytes(
+            &device,
+            &queue,
+            include_bytes!("../assets/circle.png"),
+            "circle texture",
+        )
+        .expect("Failed to load circle texture");
+        let texture_bind_group_layout = texture::Texture::bind_group_layout(&device);
+        let texture_bind_group = circle_texture.bind_group(&device, &texture_bind_group_layout);
+
         // Update pipeline layout to include the bind group layout
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &[&bind_group_layout, &texture_bind_group_layout],
             push_constant_ranges: &[],
         });
 
@@ -231,7 +596,7 @@ impl App {
             vertex: wgpu::VertexState {
                 module: &shader,
                 entry_point: "vs_main",
-                buffers: &[Vertex::desc()],
+                buffers: &[Vertex::desc(), InstanceRaw::desc()],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
             fragment: Some(wgpu::FragmentState {
@@ -249,7 +614,13 @@ impl App {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
             cache: None,
@@ -258,7 +629,9 @@ impl App {
         // Configure surface
         let size = window.inner_size();
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // COPY_DST lets the swapchain texture be written directly by the
+            // empty-post_passes fallback copy in `render_frame`.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_DST,
             format: swapchain_format,
             width: size.width,
             height: size.height,
@@ -269,6 +642,18 @@ impl App {
         };
         surface.configure(&device, &config);
 
+        let (depth_texture, depth_view) = Self::create_depth_texture(&device, &config);
+
+        // Shapes render into this offscreen target; the post-processing
+        // chain then samples it (see `render_frame`) instead of the shape
+        // pass writing straight to the swapchain.
+        let (offscreen_texture, offscreen_view) =
+            Self::create_render_target(&device, swapchain_format, &config, "Offscreen Target");
+        let ping_pong: Vec<(wgpu::Texture, wgpu::TextureView)> = (0..2)
+            .map(|i| Self::create_render_target(&device, swapchain_format, &config, &format!("Post Ping-Pong {i}")))
+            .collect();
+        let (ping_pong_textures, ping_pong_views): (Vec<_>, Vec<_>) = ping_pong.into_iter().unzip();
+
         // Store everything
         self.window = Some(window);
         self.surface = Some(surface);
@@ -276,20 +661,35 @@ impl App {
         self.queue = Some(queue);
         self.render_pipeline = Some(render_pipeline);
         self.vertex_buffer = Some(vertex_buffer);
+        self.index_buffer = Some(index_buffer);
+        self.instance_buffer = Some(instance_buffer);
         self.config = Some(config);
         self.uniform_buffer = Some(uniform_buffer);
+        self.mouse_buffer = Some(mouse_buffer);
         self.uniform_bind_group = Some(uniform_bind_group);
+        self.depth_texture = Some(depth_texture);
+        self.depth_view = Some(depth_view);
+        self.circle_texture = Some(circle_texture);
+        self.texture_bind_group = Some(texture_bind_group);
+        self.offscreen_texture = Some(offscreen_texture);
+        self.offscreen_view = Some(offscreen_view);
+        self.ping_pong_textures = ping_pong_textures;
+        self.ping_pong_views = ping_pong_views;
+
+        // Built-in post passes; callers can add more via `push_post_pass`.
+        self.push_post_pass("CRT Scanline", &post::crt_scanline_wgsl());
+        self.push_post_pass("Gaussian Blur", &post::gaussian_blur_wgsl());
     }
 
     fn render_frame(&self) {
-        if let (Some(surface), Some(device), Some(queue), Some(render_pipeline), Some(vertex_buffer), Some(uniform_bind_group)) =
-            (&self.surface, &self.device, &self.queue, &self.render_pipeline, &self.vertex_buffer, &self.uniform_bind_group)
+        if let (Some(surface), Some(device), Some(queue), Some(render_pipeline), Some(vertex_buffer), Some(index_buffer), Some(instance_buffer), Some(uniform_bind_group), Some(depth_view), Some(texture_bind_group), Some(offscreen_view)) =
+            (&self.surface, &self.device, &self.queue, &self.render_pipeline, &self.vertex_buffer, &self.index_buffer, &self.instance_buffer, &self.uniform_bind_group, &self.depth_view, &self.texture_bind_group, &self.offscreen_view)
         {
             // Get texture for current frame
             let frame = surface
                 .get_current_texture()
                 .expect("Failed to acquire next swap chain texture");
-            
+
             // Create texture view
             let view = frame
                 .texture
@@ -300,12 +700,14 @@ impl App {
                 label: Some("Render Encoder"),
             });
 
-            // Begin render pass
+            // Shape pass: render into the offscreen target rather than the
+            // swapchain, so the post-processing chain below has something to
+            // sample from.
             {
                 let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Render Pass"),
+                    label: Some("Shape Pass"),
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &view,
+                        view: offscreen_view,
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Clear(wgpu::Color {
@@ -317,15 +719,44 @@ impl App {
                             store: wgpu::StoreOp::Store,
                         },
                     })],
-                    depth_stencil_attachment: None,
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: depth_view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(1.0),
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    }),
                     timestamp_writes: None,
                     occlusion_query_set: None,
                 });
 
                 render_pass.set_pipeline(render_pipeline);
                 render_pass.set_bind_group(0, uniform_bind_group, &[]);
+                render_pass.set_bind_group(1, texture_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-                render_pass.draw(0..self.num_vertices, 0..1);
+                render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+                render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                render_pass.draw_indexed(0..self.num_indices, 0, 0..self.num_instances);
+            }
+
+            // Post-processing chain: each pass samples the previous pass's
+            // output and writes to a ping-pong target, except the final pass
+            // which writes straight to the swapchain view.
+            if self.post_passes.is_empty() {
+                encoder.copy_texture_to_texture(
+                    self.offscreen_texture.as_ref().unwrap().as_image_copy(),
+                    frame.texture.as_image_copy(),
+                    frame.texture.size(),
+                );
+            } else {
+                let mut source_view = offscreen_view;
+                let last = self.post_passes.len() - 1;
+                for (i, pass) in self.post_passes.iter().enumerate() {
+                    let target_view = if i == last { &view } else { &self.ping_pong_views[i % 2] };
+                    pass.run(device, &mut encoder, source_view, target_view);
+                    source_view = target_view;
+                }
             }
 
             // Submit command buffer and present frame
@@ -356,21 +787,46 @@ impl ApplicationHandler for App {
                     config.width = new_size.width.max(1);
                     config.height = new_size.height.max(1);
                     surface.configure(device, config);
+                    let (depth_texture, depth_view) = Self::create_depth_texture(device, config);
+                    self.depth_texture = Some(depth_texture);
+                    self.depth_view = Some(depth_view);
+
+                    let format = config.format;
+                    let (offscreen_texture, offscreen_view) =
+                        Self::create_render_target(device, format, config, "Offscreen Target");
+                    self.offscreen_texture = Some(offscreen_texture);
+                    self.offscreen_view = Some(offscreen_view);
+                    let ping_pong: Vec<(wgpu::Texture, wgpu::TextureView)> = (0..2)
+                        .map(|i| Self::create_render_target(device, format, config, &format!("Post Ping-Pong {i}")))
+                        .collect();
+                    let (ping_pong_textures, ping_pong_views): (Vec<_>, Vec<_>) = ping_pong.into_iter().unzip();
+                    self.ping_pong_textures = ping_pong_textures;
+                    self.ping_pong_views = ping_pong_views;
+
                     // Update aspect ratio when window is resized
-                    self.update_uniform_buffer(new_size.width, new_size.height);
+                    self.camera.aspect = new_size.width.max(1) as f32 / new_size.height.max(1) as f32;
+                    self.update_uniform_buffer();
                     self.window.as_ref().unwrap().request_redraw();
                 }
             }
             WindowEvent::RedrawRequested => {
+                self.camera_controller.update_camera(&mut self.camera);
+                self.update_uniform_buffer();
                 self.render_frame();
             }
             WindowEvent::KeyboardInput { device_id: _, event, is_synthetic: _ } => {
-                if event.state == winit::event::ElementState::Pressed {
-                    println!("Key pressed: {:?}", event.physical_key);
+                if let PhysicalKey::Code(key_code) = event.physical_key {
+                    let pressed = event.state == winit::event::ElementState::Pressed;
+                    if self.camera_controller.process_keyboard(key_code, pressed) {
+                        self.window.as_ref().unwrap().request_redraw();
+                    } else if pressed {
+                        println!("Key pressed: {:?}", event.physical_key);
+                    }
                 }
             }
             WindowEvent::CursorMoved { device_id: _, position } => {
                 self.mouse_state.position = self.window_position_to_ndc(&position);
+                self.update_mouse_buffer();
                 self.window.as_ref().unwrap().request_redraw();
             }
             _ => (),
@@ -385,3 +841,58 @@ fn main() {
     let mut app = App::default();
     let _ = event_loop.run_app(&mut app);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with_size(width: u32, height: u32) -> App {
+        let mut app = App::default();
+        app.config = Some(wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            width,
+            height,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 1,
+        });
+        app
+    }
+
+    #[test]
+    fn window_position_to_ndc_maps_corners_and_center() {
+        let app = app_with_size(200, 100);
+
+        assert_eq!(app.window_position_to_ndc(&PhysicalPosition::new(0.0, 0.0)), [-1.0, 1.0]);
+        assert_eq!(app.window_position_to_ndc(&PhysicalPosition::new(200.0, 100.0)), [1.0, -1.0]);
+        assert_eq!(app.window_position_to_ndc(&PhysicalPosition::new(100.0, 50.0)), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn generate_mesh_returns_empty_below_three_segments() {
+        for segments in 0..3 {
+            let shape = Shape::Circle { center: [0.0, 0.0], radius: 1.0, segments };
+            let (vertices, indices) = shape.generate_mesh();
+            assert!(vertices.is_empty());
+            assert!(indices.is_empty());
+        }
+    }
+
+    #[test]
+    fn generate_mesh_produces_one_vertex_per_segment_plus_center() {
+        let shape = Shape::Circle { center: [0.0, 0.0], radius: 1.0, segments: 8 };
+        let (vertices, indices) = shape.generate_mesh();
+        assert_eq!(vertices.len(), 9);
+        assert_eq!(indices.len(), 8 * 3);
+    }
+
+    #[test]
+    fn generate_mesh_fan_wraps_last_segment_back_to_first() {
+        let shape = Shape::Circle { center: [0.0, 0.0], radius: 1.0, segments: 5 };
+        let (_, indices) = shape.generate_mesh();
+        let last_triangle = &indices[indices.len() - 3..];
+        assert_eq!(last_triangle, &[0, 5, 1]);
+    }
+}
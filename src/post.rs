@@ -0,0 +1,255 @@
+use wgpu::util::DeviceExt;
+
+// Per-pass parameter block sampled by every built-in post shader. Kept small
+// and generic (time + reserved floats) so a pass can be swapped out without
+// the uniform layout changing.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PostParams {
+    pub time: f32,
+    pub _padding: [f32; 3],
+}
+
+// One stage of the post-processing chain (modeled on librashader's filter
+// chain): samples the previous pass's output texture and writes into this
+// pass's own render target via a fullscreen triangle.
+pub struct PostPass {
+    pub name: String,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    params_buffer: wgpu::Buffer,
+}
+
+impl PostPass {
+    pub fn new(device: &wgpu::Device, target_format: wgpu::TextureFormat, name: &str, wgsl_src: &str) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(name),
+            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(wgsl_src.to_string())),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Post Pass Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Post Pass Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(name),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(target_format.into())],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Post Pass Params"),
+            contents: bytemuck::cast_slice(&[PostParams { time: 0.0, _padding: [0.0; 3] }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self {
+            name: name.to_string(),
+            pipeline,
+            bind_group_layout,
+            sampler,
+            params_buffer,
+        }
+    }
+
+    // Public extension point for callers that want to drive per-pass params
+    // (e.g. an animated `time`); no current pass varies its params yet.
+    #[allow(dead_code)]
+    pub fn set_params(&self, queue: &wgpu::Queue, params: PostParams) {
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn bind_group(&self, device: &wgpu::Device, input_view: &wgpu::TextureView) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Post Pass Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+            ],
+        })
+    }
+
+    // Samples `input_view` and writes the fullscreen result into `target_view`.
+    pub fn run(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        input_view: &wgpu::TextureView,
+        target_view: &wgpu::TextureView,
+    ) {
+        let bind_group = self.bind_group(device, input_view);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&self.name),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        // Fullscreen triangle: vertices are generated from the builtin vertex
+        // index in the shader, no vertex buffer needed.
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+// Shared fullscreen-triangle vertex stage plus the sampled-texture/params
+// bindings every built-in post shader needs.
+const POST_PASS_PRELUDE: &str = r#"
+@group(0) @binding(0) var input_texture: texture_2d<f32>;
+@group(0) @binding(1) var input_sampler: sampler;
+struct PostParams {
+    time: f32,
+};
+@group(0) @binding(2) var<uniform> params: PostParams;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((vertex_index << 1u) & 2u);
+    let y = f32(vertex_index & 2u);
+    out.uv = vec2<f32>(x, y);
+    out.clip_position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    return out;
+}
+"#;
+
+// CRT-style scanlines plus a vignette darkening the frame edges.
+pub fn crt_scanline_wgsl() -> String {
+    format!(
+        r#"{prelude}
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {{
+    var color = textureSample(input_texture, input_sampler, in.uv).rgb;
+
+    let scanline = sin(in.uv.y * 800.0) * 0.08;
+    color -= scanline;
+
+    let centered = in.uv - vec2<f32>(0.5, 0.5);
+    let vignette = 1.0 - dot(centered, centered) * 1.2;
+    color *= clamp(vignette, 0.0, 1.0);
+
+    return vec4<f32>(color, 1.0);
+}}
+"#,
+        prelude = POST_PASS_PRELUDE
+    )
+}
+
+// Small 5-tap separable-in-a-single-pass gaussian blur.
+pub fn gaussian_blur_wgsl() -> String {
+    format!(
+        r#"{prelude}
+const BLUR_RADIUS: f32 = 0.0025;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {{
+    let weights = array<f32, 5>(0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+    var result = textureSample(input_texture, input_sampler, in.uv).rgb * weights[0];
+
+    for (var i = 1; i < 5; i++) {{
+        let offset = vec2<f32>(f32(i) * BLUR_RADIUS, 0.0);
+        result += textureSample(input_texture, input_sampler, in.uv + offset).rgb * weights[i];
+        result += textureSample(input_texture, input_sampler, in.uv - offset).rgb * weights[i];
+    }}
+
+    return vec4<f32>(result, 1.0);
+}}
+"#,
+        prelude = POST_PASS_PRELUDE
+    )
+}